@@ -1,11 +1,18 @@
 use rayon::prelude::*;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// 使用位运算枚举算法查找子集和（直接实现，不依赖Python）
-/// 
+///
 /// 适用于较小规模的数据集(n≤25)
-/// 时间复杂度: O(2^n)
-/// 
+/// 时间复杂度: O(2^n)，用反射Gray码顺序遍历每个block让内层从O(n)降到O(1)：
+/// 相邻两个Gray码只有一个bit不同，`subset_sum`只需针对那一个bit做一次加/减，
+/// 而不用每个mask都重新扫一遍全部n个bit；下标向量只在命中时才按需物化。
+///
+/// 每个block把命中结果累积到自己的本地`Vec`里，整个搜索期间不加锁；所有
+/// block跑完后用`rayon`的`map`+`reduce`把各自的本地结果归并成一个`Vec`。
+/// 是否已经找到一个解（`!find_all`时提前退出用）由一个共享的`AtomicBool`
+/// 标记：内层循环只做一次`Relaxed`读，命中后`Release`写一次，不会有锁竞争。
+///
 /// # 参数
 /// * `numbers` - 整数数组
 /// * `target` - 目标和值
@@ -17,79 +24,170 @@ pub fn find_subset_sum_bit_enum_raw(
     precision: i64,
     find_all: bool,
 ) -> Vec<Vec<usize>> {
-    let results = Arc::new(Mutex::new(Vec::new()));
-    let found = Arc::new(Mutex::new(false));
-    let results_for_closure = results.clone();
-    let found_for_closure = found.clone();
-    
     // 获取CPU核心数，用于并行计算
     let n = numbers.len();
+    let bits = std::cmp::min(n, 64);
     let max_combinations = if n >= 30 { 1u64 << 30 } else { 1u64 << n };
-    
+
     // 将任务分割成多个块，以便并行处理
     let num_cpus = num_cpus::get() as u64;
     let block_size = max_combinations / num_cpus + 1;
-    
-    // 并行处理每个块
-    (0..num_cpus).into_par_iter().for_each(|cpu_id| {
-        let start = cpu_id * block_size;
-        let end = std::cmp::min(start + block_size, max_combinations);
-        
-        // 处理当前块中的所有组合
-        for mask in start..end {
-            // 如果只需要找到一个解且已经找到，则提前退出
-            if !find_all && *found_for_closure.lock().unwrap() {
-                break;
+
+    // 只需要一个解时，命中后用它通知其它block尽快停止
+    let found = AtomicBool::new(false);
+
+    // 并行处理每个块，每个block把命中结果收进自己的本地Vec，最后统一归并
+    let mut final_results: Vec<Vec<usize>> = (0..num_cpus)
+        .into_par_iter()
+        .map(|cpu_id| {
+            let start = cpu_id * block_size;
+            let end = std::cmp::min(start + block_size, max_combinations);
+            let mut local_results: Vec<Vec<usize>> = Vec::new();
+            if start >= end {
+                return local_results;
             }
-            
-            let mut subset_sum = 0;
-            let mut indices = Vec::new();
-            
-            // 计算当前组合的和
-            for i in 0..std::cmp::min(n, 64) {
-                if (mask & (1 << i)) != 0 {
+
+            // block起点的Gray码和它对应的子集和，只在这里做一次O(n)计算
+            let mut prev_gray = start ^ (start >> 1);
+            let mut subset_sum: i64 = 0;
+            for i in 0..bits {
+                if (prev_gray & (1 << i)) != 0 {
                     subset_sum += numbers[i];
-                    indices.push(i);
                 }
             }
-            
-            // 检查是否满足目标和（考虑精度）
-            let is_match = if precision == 0 {
-                subset_sum == target
-            } else {
-                (subset_sum - target).abs() <= precision
-            };
-
-            if is_match {
-                let mut results_guard = results_for_closure.lock().unwrap();
-                results_guard.push(indices);
-                
-                if !find_all {
-                    let mut found_guard = found_for_closure.lock().unwrap();
-                    *found_guard = true;
+
+            // 处理当前块中的所有组合
+            for mask in start..end {
+                // 如果只需要找到一个解且已经找到，则提前退出
+                if !find_all && found.load(Ordering::Relaxed) {
                     break;
                 }
+
+                let gray = mask ^ (mask >> 1);
+                if mask > start {
+                    // 连续的Gray码只有一个bit不同，异或定位出那个bit后单次加/减更新subset_sum
+                    let changed = gray ^ prev_gray;
+                    let bit = changed.trailing_zeros() as usize;
+                    if bit < bits {
+                        if (gray & changed) != 0 {
+                            subset_sum += numbers[bit];
+                        } else {
+                            subset_sum -= numbers[bit];
+                        }
+                    }
+                    prev_gray = gray;
+                }
+
+                // 检查是否满足目标和（考虑精度）
+                let is_match = if precision == 0 {
+                    subset_sum == target
+                } else {
+                    (subset_sum - target).abs() <= precision
+                };
+
+                if is_match {
+                    // 只有命中时才物化下标向量
+                    let indices: Vec<usize> = (0..bits).filter(|&i| (gray & (1 << i)) != 0).collect();
+                    local_results.push(indices);
+
+                    if !find_all {
+                        found.store(true, Ordering::Release);
+                        break;
+                    }
+                }
             }
-        }
-    });
-    
-    // 返回结果 - 修改此部分以避免try_unwrap导致的线程恐慌
-    let final_results = {
-        let guard = results.lock().unwrap();
-        guard.clone()  // 直接克隆锁内的数据，而不是尝试unwrap Arc
-    };
-    
+
+            local_results
+        })
+        .reduce(Vec::new, |mut a, b| {
+            a.extend(b);
+            a
+        });
+
     // 如果只需要一个解且找到了多个，只返回第一个
     if !find_all && final_results.len() > 1 {
-        return vec![final_results[0].clone()];
+        final_results.truncate(1);
     }
-    
+
     final_results
 }
 
+/// 只统计满足条件的子集数量，不materialize下标向量（直接实现，不依赖Python）
+///
+/// 调用方只关心"有多少个子集命中target"时，避免`find_subset_sum_bit_enum_raw`
+/// 为每个命中分配`Vec<usize>`并最后归并这些向量的开销：每个并行block用
+/// 局部`u64`计数器累加，最后用rayon的`sum`归并，不涉及共享锁。
+///
+/// 和`find_subset_sum_bit_enum_raw`一样用反射Gray码顺序遍历每个block，
+/// 单bit翻转增量更新`subset_sum`，把内层从O(n)降到O(1)；Gray码维护和
+/// `_raw`版本相同，只是命中时不需要materialize下标向量。
+///
+/// # 参数
+/// * `numbers` - 整数数组
+/// * `target` - 目标和值
+/// * `precision` - 精度（绝对值）
+pub fn find_subset_sum_bit_enum_count_raw(numbers: &[i64], target: i64, precision: i64) -> u64 {
+    let n = numbers.len();
+    let bits = std::cmp::min(n, 64);
+    let max_combinations = if n >= 30 { 1u64 << 30 } else { 1u64 << n };
+
+    let num_cpus = num_cpus::get() as u64;
+    let block_size = max_combinations / num_cpus + 1;
+
+    (0..num_cpus)
+        .into_par_iter()
+        .map(|cpu_id| {
+            let start = cpu_id * block_size;
+            let end = std::cmp::min(start + block_size, max_combinations);
+            let mut local_count: u64 = 0;
+            if start >= end {
+                return local_count;
+            }
+
+            // block起点的Gray码和它对应的子集和，只在这里做一次O(n)计算
+            let mut prev_gray = start ^ (start >> 1);
+            let mut subset_sum: i64 = 0;
+            for i in 0..bits {
+                if (prev_gray & (1 << i)) != 0 {
+                    subset_sum += numbers[i];
+                }
+            }
+
+            for mask in start..end {
+                let gray = mask ^ (mask >> 1);
+                if mask > start {
+                    // 连续的Gray码只有一个bit不同，异或定位出那个bit后单次加/减更新subset_sum
+                    let changed = gray ^ prev_gray;
+                    let bit = changed.trailing_zeros() as usize;
+                    if bit < bits {
+                        if (gray & changed) != 0 {
+                            subset_sum += numbers[bit];
+                        } else {
+                            subset_sum -= numbers[bit];
+                        }
+                    }
+                    prev_gray = gray;
+                }
+
+                let is_match = if precision == 0 {
+                    subset_sum == target
+                } else {
+                    (subset_sum - target).abs() <= precision
+                };
+
+                if is_match {
+                    local_count += 1;
+                }
+            }
+
+            local_count
+        })
+        .sum()
+}
+
 /*
 /// 使用位运算枚举算法查找子集和
-/// 
+///
 /// 适用于较小规模的数据集(n≤25)
 /// 时间复杂度: O(2^n)
 /// 
@@ -121,9 +219,9 @@ mod tests {
         let precision = 0;
         
         let results = find_subset_sum_bit_enum_raw(&numbers, target, precision, true);
-        
-        // 应该有两个解：[2,3,4] 和 [4,5]
-        assert_eq!(results.len(), 2);
+
+        // 应该有三个解：[1,3,5]、[2,3,4] 和 [4,5]
+        assert_eq!(results.len(), 3);
         
         // 验证结果
         let sums: Vec<i64> = results.iter()
@@ -134,4 +232,43 @@ mod tests {
             assert_eq!(sum, target);
         }
     }
+
+    #[test]
+    fn test_bit_enum_count_matches_raw_len() {
+        let numbers = vec![1, 2, 3, 4, 5];
+        let target = 9;
+        let precision = 0;
+
+        let results = find_subset_sum_bit_enum_raw(&numbers, target, precision, true);
+        let count = find_subset_sum_bit_enum_count_raw(&numbers, target, precision);
+
+        assert_eq!(count, results.len() as u64);
+    }
+
+    #[test]
+    fn test_bit_enum_gray_code_sums_match_brute_force() {
+        // 用一组不按2的幂排列的数字覆盖多个block边界上的Gray码转换
+        let numbers = vec![3, 7, 1, 9, 2, 5, 11];
+        let target = 12;
+        let precision = 1;
+
+        let results = find_subset_sum_bit_enum_raw(&numbers, target, precision, true);
+
+        for indices in &results {
+            let sum: i64 = indices.iter().map(|&i| numbers[i]).sum();
+            assert!((sum - target).abs() <= precision);
+        }
+
+        // 暴力枚举核对命中数量，确保Gray码增量求和没有算错和值
+        let n = numbers.len();
+        let mut brute_force_count = 0;
+        for mask in 0..(1u64 << n) {
+            let sum: i64 = (0..n).filter(|&i| (mask & (1 << i)) != 0).map(|i| numbers[i]).sum();
+            if (sum - target).abs() <= precision {
+                brute_force_count += 1;
+            }
+        }
+
+        assert_eq!(results.len(), brute_force_count);
+    }
 }
@@ -1,5 +1,7 @@
-use std::collections::HashMap;
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
 /// 使用分支限界法查找子集和（直接实现，不依赖Python）
@@ -20,7 +22,11 @@ pub fn find_subset_sum_branch_bound_raw(
 ) -> Vec<Vec<usize>> {
     let results = Arc::new(Mutex::new(Vec::new()));
     let results_for_closure = results.clone();
-    
+
+    // 一旦某个worker找到一个解（!find_all时），其它worker应尽快停止，
+    // 而不是把各自分到的搜索块跑完
+    let cancel = Arc::new(AtomicBool::new(false));
+
     // 排序并创建索引映射
     let mut sorted_numbers: Vec<(i64, usize)> = numbers.iter()
         .enumerate()
@@ -33,26 +39,282 @@ pub fn find_subset_sum_branch_bound_raw(
     let sorted_values: Vec<i64> = sorted_numbers.iter().map(|&(val, _)| val).collect();
     let indices_map: Vec<usize> = sorted_numbers.iter().map(|&(_, idx)| idx).collect();
     
+    // 预计算后缀和：suffix_pos[k]/suffix_neg[k] 分别是 sorted_values[k..] 中正数/负数之和
+    // 避免 serial_branch_and_bound 在每个节点都重新扫描一遍剩余元素
+    let n = sorted_values.len();
+    let mut suffix_pos = vec![0i64; n + 1];
+    let mut suffix_neg = vec![0i64; n + 1];
+    for i in (0..n).rev() {
+        let val = sorted_values[i];
+        suffix_pos[i] = suffix_pos[i + 1] + if val > 0 { val } else { 0 };
+        suffix_neg[i] = suffix_neg[i + 1] + if val < 0 { val } else { 0 };
+    }
+
     // 并行执行分支限界搜索
-    parallel_branch_and_bound(&sorted_values, &indices_map, target, precision, results_for_closure, find_all);
-    
+    parallel_branch_and_bound(&sorted_values, &indices_map, &suffix_pos, &suffix_neg, target, precision, results_for_closure, cancel, find_all);
+
     // 返回结果 - 修改此部分以避免try_unwrap导致的线程恐慌
     let final_results = {
         let guard = results.lock().unwrap();
         guard.clone()  // 直接克隆锁内的数据，而不是尝试unwrap Arc
     };
-    
+
+    // find_all时不同并行任务可能各自重建出同一个子集，规范化（排序）后去重
+    let final_results = if find_all {
+        let mut seen = HashSet::new();
+        let mut deduped = Vec::with_capacity(final_results.len());
+        for mut indices in final_results {
+            indices.sort_unstable();
+            if seen.insert(indices.clone()) {
+                deduped.push(indices);
+            }
+        }
+        deduped
+    } else {
+        final_results
+    };
+
     // 如果只需要一个解且找到了多个，只返回第一个
     if !find_all && final_results.len() > 1 {
         return vec![final_results[0].clone()];
     }
-    
+
     final_results
 }
 
+/// 只统计满足条件的子集数量，不materialize下标向量（直接实现，不依赖Python）
+///
+/// 沿用和`find_subset_sum_branch_bound_raw`一样的排序+后缀和剪枝，但递归时
+/// 不再携带`current_path`、不写共享`Vec`，只返回命中数量，省掉了每个节点
+/// clone路径向量、以及汇总阶段加锁的开销。前`min(ceil(n/4), 10)`层仍展开
+/// 成并行任务，用`rayon`的`map`+`sum`归并各任务的计数。
+///
+/// # 参数
+/// * `numbers` - 整数数组
+/// * `target` - 目标和值
+/// * `precision` - 精度（绝对值）
+pub fn find_subset_sum_branch_bound_count_raw(numbers: &[i64], target: i64, precision: i64) -> u64 {
+    let mut sorted_values: Vec<i64> = numbers.to_vec();
+    sorted_values.sort_by(|a, b| b.abs().cmp(&a.abs()));
+
+    let n = sorted_values.len();
+    let mut suffix_pos = vec![0i64; n + 1];
+    let mut suffix_neg = vec![0i64; n + 1];
+    for i in (0..n).rev() {
+        let val = sorted_values[i];
+        suffix_pos[i] = suffix_pos[i + 1] + if val > 0 { val } else { 0 };
+        suffix_neg[i] = suffix_neg[i + 1] + if val < 0 { val } else { 0 };
+    }
+
+    let parallel_depth = std::cmp::min((n as f64 / 4.0).ceil() as usize, 10);
+    let parallel_depth = std::cmp::min(parallel_depth, n);
+
+    let mut tasks: Vec<(usize, i64)> = Vec::new();
+    generate_count_tasks(0, parallel_depth, 0, &sorted_values, &mut tasks);
+
+    tasks
+        .into_par_iter()
+        .map(|(depth, sum)| {
+            count_branch_and_bound(&sorted_values, &suffix_pos, &suffix_neg, depth, sum, target, precision)
+        })
+        .sum()
+}
+
+/// 递归生成计数用的初始任务：只携带到达`max_depth`层时的和值，不携带路径
+fn generate_count_tasks(
+    depth: usize,
+    max_depth: usize,
+    current_sum: i64,
+    numbers: &[i64],
+    tasks: &mut Vec<(usize, i64)>,
+) {
+    if depth == max_depth {
+        tasks.push((depth, current_sum));
+        return;
+    }
+
+    generate_count_tasks(depth + 1, max_depth, current_sum, numbers, tasks);
+    generate_count_tasks(depth + 1, max_depth, current_sum + numbers[depth], numbers, tasks);
+}
+
+/// 串行分支定界计数：和`serial_branch_and_bound`共享剪枝逻辑，但只累加命中数
+fn count_branch_and_bound(
+    numbers: &[i64],
+    suffix_pos: &[i64],
+    suffix_neg: &[i64],
+    depth: usize,
+    current_sum: i64,
+    target: i64,
+    precision: i64,
+) -> u64 {
+    let n = numbers.len();
+
+    if depth == n {
+        let is_match = if precision == 0 {
+            current_sum == target
+        } else {
+            (current_sum - target).abs() <= precision
+        };
+        return if is_match { 1 } else { 0 };
+    }
+
+    let remaining_sum = suffix_pos[depth];
+    let remaining_negative = suffix_neg[depth];
+    let prunable = if precision == 0 {
+        current_sum + remaining_sum < target || current_sum + remaining_negative > target
+    } else {
+        current_sum + remaining_sum < target - precision || current_sum + remaining_negative > target + precision
+    };
+    if prunable {
+        return 0;
+    }
+
+    count_branch_and_bound(numbers, suffix_pos, suffix_neg, depth + 1, current_sum, target, precision)
+        + count_branch_and_bound(
+            numbers,
+            suffix_pos,
+            suffix_neg,
+            depth + 1,
+            current_sum + numbers[depth],
+            target,
+            precision,
+        )
+}
+
+/// 最优先搜索(best-first)的分支限界：用二叉堆作为搜索前沿，而不是DFS递归栈，
+/// 优先展开"乐观完成边界离target最近"的部分解（直接实现，不依赖Python）
+///
+/// 只返回一个解，没有`find_all`语义——对`find_all=false`的场景，这种模式
+/// 通常比`find_subset_sum_branch_bound_raw`的DFS更快找到第一个解，代价是
+/// 前沿堆可能比DFS的调用栈占用更多内存；`find_all=true`时请继续使用DFS版本
+/// （`find_subset_sum_branch_bound_raw`），这里不提供"找到所有解"的模式。
+///
+/// 乐观边界：`[current_sum+剩余负数之和, current_sum+剩余正数之和]`是从当前
+/// 部分解出发还能达到的和值范围；优先级取该范围到`target`的距离（范围内含
+/// target时为0，最优先展开），和DFS共用同一套后缀和剪枝表。
+///
+/// # 参数
+/// * `numbers` - 整数数组
+/// * `target` - 目标和值
+/// * `precision` - 精度（绝对值）
+pub fn find_subset_sum_branch_bound_best_first_raw(
+    numbers: &[i64],
+    target: i64,
+    precision: i64,
+) -> Vec<Vec<usize>> {
+    let mut sorted_numbers: Vec<(i64, usize)> = numbers.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+    sorted_numbers.sort_by(|a, b| b.0.abs().cmp(&a.0.abs()));
+
+    let sorted_values: Vec<i64> = sorted_numbers.iter().map(|&(v, _)| v).collect();
+    let indices_map: Vec<usize> = sorted_numbers.iter().map(|&(_, idx)| idx).collect();
+
+    let n = sorted_values.len();
+    let mut suffix_pos = vec![0i64; n + 1];
+    let mut suffix_neg = vec![0i64; n + 1];
+    for i in (0..n).rev() {
+        let val = sorted_values[i];
+        suffix_pos[i] = suffix_pos[i + 1] + if val > 0 { val } else { 0 };
+        suffix_neg[i] = suffix_neg[i + 1] + if val < 0 { val } else { 0 };
+    }
+
+    // 乐观边界到target的距离：范围内含target时为0（最优先展开）
+    let bound_distance = |depth: usize, current_sum: i64| -> i64 {
+        let upper = current_sum + suffix_pos[depth];
+        let lower = current_sum + suffix_neg[depth];
+        if target < lower {
+            lower - target
+        } else if target > upper {
+            target - upper
+        } else {
+            0
+        }
+    };
+
+    let mut frontier = BinaryHeap::new();
+    frontier.push(FrontierState {
+        priority: bound_distance(0, 0),
+        depth: 0,
+        current_sum: 0,
+        path: Vec::new(),
+    });
+
+    while let Some(state) = frontier.pop() {
+        let is_match = if precision == 0 {
+            state.current_sum == target
+        } else {
+            (state.current_sum - target).abs() <= precision
+        };
+
+        if is_match {
+            let mut original_indices: Vec<usize> = state.path.iter().map(|&idx| indices_map[idx]).collect();
+            original_indices.sort_unstable();
+            return vec![original_indices];
+        }
+
+        if state.depth == n {
+            continue;
+        }
+
+        // 乐观边界都够不到[target-precision, target+precision]，剪掉这个分支
+        if bound_distance(state.depth, state.current_sum) > precision {
+            continue;
+        }
+
+        // 不选当前元素
+        frontier.push(FrontierState {
+            priority: bound_distance(state.depth + 1, state.current_sum),
+            depth: state.depth + 1,
+            current_sum: state.current_sum,
+            path: state.path.clone(),
+        });
+
+        // 选当前元素
+        let mut with_current = state.path.clone();
+        with_current.push(state.depth);
+        let new_sum = state.current_sum + sorted_values[state.depth];
+        frontier.push(FrontierState {
+            priority: bound_distance(state.depth + 1, new_sum),
+            depth: state.depth + 1,
+            current_sum: new_sum,
+            path: with_current,
+        });
+    }
+
+    Vec::new()
+}
+
+/// best-first搜索前沿中的一个部分解；`priority`越小越优先展开
+struct FrontierState {
+    priority: i64,
+    depth: usize,
+    current_sum: i64,
+    path: Vec<usize>,
+}
+
+impl PartialEq for FrontierState {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl Eq for FrontierState {}
+
+impl PartialOrd for FrontierState {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FrontierState {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        // BinaryHeap是大顶堆，priority(距离)越小越该被先弹出，所以反转比较
+        other.priority.cmp(&self.priority)
+    }
+}
+
 /*
 /// 使用分支限界法查找子集和
-/// 
+///
 /// 适用于大规模数据集(n>40)
 /// 平均时间复杂度优于O(2^n)
 /// 
@@ -77,9 +339,12 @@ pub fn find_subset_sum_branch_bound(
 fn parallel_branch_and_bound(
     numbers: &[i64],
     indices_map: &[usize],
+    suffix_pos: &[i64],
+    suffix_neg: &[i64],
     target: i64,
     precision: i64,
     results: Arc<Mutex<Vec<Vec<usize>>>>,
+    cancel: Arc<AtomicBool>,
     find_all: bool,
 ) {
     let n = numbers.len();
@@ -122,6 +387,11 @@ fn parallel_branch_and_bound(
     
     // 并行执行任务
     tasks.into_par_iter().for_each(|(sum, path)| {
+        // 如果别的worker已经找到解且只需要一个解，跳过整个任务
+        if !find_all && cancel.load(Ordering::Acquire) {
+            return;
+        }
+
         // 对每个任务执行串行分支定界
         let mut local_results = Vec::new();
         serial_branch_and_bound(
@@ -131,19 +401,24 @@ fn parallel_branch_and_bound(
             path,
             numbers,
             indices_map,
+            suffix_pos,
+            suffix_neg,
             target,
             precision,
             &mut local_results,
+            &cancel,
             find_all,
         );
-        
+
         // 合并结果
         if !local_results.is_empty() {
             let mut results_guard = results.lock().unwrap();
             results_guard.extend(local_results);
-            
-            // 如果只需要一个解且已找到，可以提前退出
-            // 但由于并行执行，可能会找到多个解
+
+            // 只需要一个解时，通知其它worker尽快停止
+            if !find_all {
+                cancel.store(true, Ordering::Release);
+            }
         }
     });
 }
@@ -156,11 +431,19 @@ fn serial_branch_and_bound(
     current_path: Vec<usize>,
     numbers: &[i64],
     indices_map: &[usize],
+    suffix_pos: &[i64],
+    suffix_neg: &[i64],
     target: i64,
     precision: i64,
     results: &mut Vec<Vec<usize>>,
+    cancel: &AtomicBool,
     find_all: bool,
 ) {
+    // 别的worker已经找到解，无需再继续探索这个分支
+    if !find_all && cancel.load(Ordering::Acquire) {
+        return;
+    }
+
     // 检查当前和是否满足要求
     // 当精度为0时，要求完全匹配
     if precision == 0 {
@@ -177,9 +460,9 @@ fn serial_branch_and_bound(
         }
     }
     
-    // 计算剩余数字的上下界
-    let remaining_sum: i64 = numbers[start_depth..].iter().filter(|&&x| x > 0).sum();
-    let remaining_negative: i64 = numbers[start_depth..].iter().filter(|&&x| x < 0).sum();
+    // 计算剩余数字的上下界（O(1) 查表，取代每个节点重新扫描 numbers[start_depth..]）
+    let remaining_sum: i64 = suffix_pos[start_depth];
+    let remaining_negative: i64 = suffix_neg[start_depth];
     
     // 剪枝：如果当前和加上所有剩余正数仍小于目标值减精度，或者加上所有剩余负数仍大于目标值加精度，则剪枝
     // 当精度为0时，使用精确匹配进行剪枝
@@ -194,6 +477,10 @@ fn serial_branch_and_bound(
     
     // 递归搜索
     for depth in start_depth..n {
+        if !find_all && cancel.load(Ordering::Acquire) {
+            return;
+        }
+
         // 不选当前元素
         serial_branch_and_bound(
             depth + 1,
@@ -202,20 +489,23 @@ fn serial_branch_and_bound(
             current_path.clone(),
             numbers,
             indices_map,
+            suffix_pos,
+            suffix_neg,
             target,
             precision,
             results,
+            cancel,
             find_all,
         );
-        
+
         if !find_all && !results.is_empty() {
             return;
         }
-        
+
         // 选择当前元素
         let mut new_path = current_path.clone();
         new_path.push(indices_map[depth]);
-        
+
         serial_branch_and_bound(
             depth + 1,
             n,
@@ -223,12 +513,15 @@ fn serial_branch_and_bound(
             new_path,
             numbers,
             indices_map,
+            suffix_pos,
+            suffix_neg,
             target,
             precision,
             results,
+            cancel,
             find_all,
         );
-        
+
         if !find_all && !results.is_empty() {
             return;
         }
@@ -247,8 +540,45 @@ mod tests {
         let find_all = false;
         
         let results = find_subset_sum_branch_bound_raw(&numbers, target, precision, find_all);
-        
+
+        assert_eq!(results.len(), 1);
+        let sum: i64 = results[0].iter().map(|&i| numbers[i]).sum();
+        assert_eq!(sum, target);
+    }
+
+    #[test]
+    fn test_branch_bound_count_matches_raw_len() {
+        let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let target = 10;
+        let precision = 0;
+
+        let results = find_subset_sum_branch_bound_raw(&numbers, target, precision, true);
+        let count = find_subset_sum_branch_bound_count_raw(&numbers, target, precision);
+
+        assert_eq!(count, results.len() as u64);
+    }
+
+    #[test]
+    fn test_branch_bound_best_first_finds_valid_solution() {
+        let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let target = 10;
+        let precision = 0;
+
+        let results = find_subset_sum_branch_bound_best_first_raw(&numbers, target, precision);
+
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], vec![0, 1]);
+        let sum: i64 = results[0].iter().map(|&i| numbers[i]).sum();
+        assert_eq!(sum, target);
+    }
+
+    #[test]
+    fn test_branch_bound_best_first_no_solution() {
+        let numbers = vec![2, 4, 6];
+        let target = 3;
+        let precision = 0;
+
+        let results = find_subset_sum_branch_bound_best_first_raw(&numbers, target, precision);
+
+        assert!(results.is_empty());
     }
 }
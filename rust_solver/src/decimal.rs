@@ -0,0 +1,94 @@
+use crate::dispatch;
+
+/// 货币/定点数场景下的子集和求解入口
+///
+/// 账务对账等场景里输入是带小数的货币金额（如发票行项目匹配银行流水总额），
+/// 直接用浮点数参与比较会有精度漂移问题。这里把所有数值和目标值按 `scale`
+/// （例如 100 表示保留两位小数）放大、四舍五入为整数后，再走已有的整数
+/// `_raw` 求解器，返回的仍是原始索引集合。
+///
+/// # 参数
+/// * `numbers` - 原始（十进制）数值数组
+/// * `target` - 目标和值
+/// * `precision` - 精度（十进制下的绝对值）
+/// * `scale` - 放大系数，如 100 表示保留两位小数
+/// * `find_all` - 是否查找所有解
+/// * `algorithm` - 选用哪个求解器，取值同 `rust_find_subset_sum` 的 algorithm 参数
+///
+/// 当 `scale * max(|value|) * numbers.len()` 或 `scale * max(|target|, |precision|)`
+/// 超出 `i64` 可表示范围时返回 `Err`，避免放大后静默溢出导致错误的匹配结果——
+/// 只看 `numbers` 会漏掉 `numbers` 很小/为空但 `target`/`precision` 本身很大的情况。
+pub fn solve_subset_sum_decimal(
+    numbers: &[f64],
+    target: f64,
+    precision: f64,
+    scale: f64,
+    find_all: bool,
+    algorithm: &str,
+) -> Result<Vec<Vec<usize>>, String> {
+    if scale <= 0.0 {
+        return Err("scale必须是正数".to_string());
+    }
+
+    if scaled_values_may_overflow(numbers, target, precision, scale) {
+        return Err("scale过大，放大后的数值总和或target/precision可能溢出i64".to_string());
+    }
+
+    // 按scale放大并四舍五入为整数，而不是直接截断，减少量化误差
+    let int_numbers: Vec<i64> = numbers.iter().map(|&x| (x * scale).round() as i64).collect();
+    let int_target = (target * scale).round() as i64;
+    let int_precision = (precision * scale).round() as i64;
+
+    let result = dispatch::dispatch_algorithm(&int_numbers, int_target, int_precision, find_all, algorithm);
+
+    Ok(result)
+}
+
+/// 放大后数值总和、或放大后的target/precision本身，是否会超出`i64`可表示范围
+///
+/// `rust_find_subset_sum`（lib.rs）按`decimals`放大走C ABI入口时复用这同一条
+/// 判断，避免两处各写一份、将来改其中一处漏改另一处又悄悄放过了会溢出的输入。
+/// target/precision不参与求和，不用乘`numbers.len()`
+pub(crate) fn scaled_values_may_overflow(numbers: &[f64], target: f64, precision: f64, scale: f64) -> bool {
+    let max_abs = numbers.iter().fold(0.0_f64, |acc, &x| acc.max(x.abs()));
+    let numbers_bound = max_abs * numbers.len() as f64;
+    let target_bound = target.abs().max(precision.abs());
+    numbers_bound.max(target_bound) * scale > i64::MAX as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_scaling_matches_cents() {
+        // 发票行项目匹配银行流水总额：11.50 + 3.25 = 14.75
+        let numbers = vec![11.50, 3.25, 7.00];
+        let target = 14.75;
+        let precision = 0.0;
+
+        let results = solve_subset_sum_decimal(&numbers, target, precision, 100.0, true, "auto").unwrap();
+
+        assert!(!results.is_empty());
+        for indices in &results {
+            let sum: f64 = indices.iter().map(|&i| numbers[i]).sum();
+            assert!((sum - target).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_decimal_scaling_rejects_overflowing_scale() {
+        let numbers = vec![1e10, 2e10];
+        let result = solve_subset_sum_decimal(&numbers, 1e10, 0.0, 1e10, false, "auto");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_decimal_scaling_rejects_overflowing_target_with_tiny_numbers() {
+        // numbers为空、max_abs=0，放大后的和不会溢出，但target本身放大后会溢出，
+        // 这种情况同样要被拒绝，不能静默饱和成i64::MAX
+        let numbers: Vec<f64> = vec![];
+        let result = solve_subset_sum_decimal(&numbers, 1e20, 0.0, 1.0, false, "auto");
+        assert!(result.is_err());
+    }
+}
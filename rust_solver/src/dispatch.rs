@@ -0,0 +1,278 @@
+use crate::{bit_enum, branch_bound, dynamic_prog, fptas, meet_middle};
+
+/// `"fptas"`在`dispatch_algorithm`里显式被请求时使用的默认相对误差
+const DEFAULT_FPTAS_EPSILON: f64 = 0.1;
+
+/// 本次求解实际使用的算法，便于调用方记录日志或做benchmark
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    BitEnum,
+    MeetMiddle,
+    SchroeppelShamir,
+    Dp,
+    BranchBound,
+    Fptas,
+}
+
+impl Strategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Strategy::BitEnum => "bit_enum",
+            Strategy::MeetMiddle => "meet_middle",
+            Strategy::SchroeppelShamir => "schroeppel_shamir",
+            Strategy::Dp => "dp",
+            Strategy::BranchBound => "branch_bound",
+            Strategy::Fptas => "fptas",
+        }
+    }
+}
+
+/// 按算法名称分派到具体的`_raw`求解器，`"auto"`或未识别的名称时按规模自动选择
+pub(crate) fn dispatch_algorithm(
+    numbers: &[i64],
+    target: i64,
+    precision: i64,
+    find_all: bool,
+    algorithm: &str,
+) -> Vec<Vec<usize>> {
+    match algorithm {
+        "bit_enum" => bit_enum::find_subset_sum_bit_enum_raw(numbers, target, precision, find_all),
+        "meet_middle" => meet_middle::find_subset_sum_meet_middle_raw(numbers, target, precision, find_all),
+        "schroeppel_shamir" => meet_middle::find_subset_sum_schroeppel_shamir_raw(numbers, target, precision, find_all),
+        "dp" => dynamic_prog::find_subset_sum_dp_raw(numbers, target, precision, find_all),
+        "branch_bound" => branch_bound::find_subset_sum_branch_bound_raw(numbers, target, precision, find_all),
+        // best-first子模式：用堆前沿换取更快的time-to-first-solution，不支持find_all=true
+        "branch_bound_best_first" => branch_bound::find_subset_sum_branch_bound_best_first_raw(numbers, target, precision),
+        "fptas" => fptas::find_subset_sum_fptas_raw(numbers, target, precision, DEFAULT_FPTAS_EPSILON),
+        // "auto"或未识别的名称都落到这里，按规模自动选择
+        _ => {
+            let (result, _strategy) = solve_subset_sum(numbers, target, precision, find_all);
+            result
+        }
+    }
+}
+
+/// 按算法名称分派到具体的`_count_raw`计数器，`"auto"`或未识别的名称时按规模自动选择
+///
+/// 和`dispatch_algorithm`共用同一套`Strategy`选择逻辑，只是每个分支走的是只
+/// 返回`u64`、不materialize下标向量的计数内核。
+pub(crate) fn dispatch_algorithm_count(
+    numbers: &[i64],
+    target: i64,
+    precision: i64,
+    algorithm: &str,
+) -> u64 {
+    match algorithm {
+        "bit_enum" => bit_enum::find_subset_sum_bit_enum_count_raw(numbers, target, precision),
+        "meet_middle" => meet_middle::find_subset_sum_meet_middle_count_raw(numbers, target, precision),
+        "schroeppel_shamir" => meet_middle::find_subset_sum_schroeppel_shamir_count_raw(numbers, target, precision),
+        "dp" => dynamic_prog::find_subset_sum_dp_count_raw(numbers, target, precision),
+        "branch_bound" => branch_bound::find_subset_sum_branch_bound_count_raw(numbers, target, precision),
+        // best-first只返回一个解，没有专门的计数内核，如实统计0或1
+        "branch_bound_best_first" => {
+            branch_bound::find_subset_sum_branch_bound_best_first_raw(numbers, target, precision).len() as u64
+        }
+        "fptas" => fptas::find_subset_sum_fptas_count_raw(numbers, target, precision, DEFAULT_FPTAS_EPSILON),
+        // "auto"或未识别的名称都落到这里，按规模自动选择
+        _ => {
+            let (count, _strategy) = solve_subset_sum_count(numbers, target, precision);
+            count
+        }
+    }
+}
+
+/// 自动选择时可覆盖的规模阈值
+#[derive(Debug, Clone, Copy)]
+pub struct SolverOptions {
+    /// n不超过该值时使用位运算枚举(bit_enum)
+    pub bit_enum_max_n: usize,
+    /// n不超过该值时使用折半枚举(meet_middle)
+    pub meet_middle_max_n: usize,
+    /// target与精度之和、以及DP表宽度(max_sum-min_sum+1)都不超过该值、且所有
+    /// 数值非负时优先使用DP
+    pub dp_max_range: i64,
+    /// n超过该值、且所有数值非负时，branch_bound也扛不住，改用fptas近似求解
+    pub fptas_min_n: usize,
+    /// 走fptas分支时使用的相对误差
+    pub fptas_epsilon: f64,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        SolverOptions {
+            bit_enum_max_n: 25,
+            meet_middle_max_n: 40,
+            dp_max_range: 100_000,
+            fptas_min_n: 60,
+            fptas_epsilon: DEFAULT_FPTAS_EPSILON,
+        }
+    }
+}
+
+/// 统一的子集和求解入口：根据n、target量级、数值是否全非负自动选择最合适的算法
+///
+/// 几个`_raw`求解器各自在文档里标注了适用的规模区间（bit_enum适合n<=25，
+/// meet_middle适合25<n<=40，branch_bound适合40<n<=fptas_min_n，dp适合target
+/// 较小的非负整数问题，fptas适合branch_bound也扛不住的超大规模非负整数
+/// 输入，只给近似解），调用方原本需要自己判断落在哪个区间。这里统一做这件
+/// 事，并把实际选用的`Strategy`一并返回，方便调用方记录日志或做benchmark。
+/// 阈值可以通过`solve_subset_sum_with_options`覆盖。
+pub fn solve_subset_sum(
+    numbers: &[i64],
+    target: i64,
+    precision: i64,
+    find_all: bool,
+) -> (Vec<Vec<usize>>, Strategy) {
+    solve_subset_sum_with_options(numbers, target, precision, find_all, &SolverOptions::default())
+}
+
+/// 同`solve_subset_sum`，允许调用方覆盖自动选择的阈值
+pub fn solve_subset_sum_with_options(
+    numbers: &[i64],
+    target: i64,
+    precision: i64,
+    find_all: bool,
+    options: &SolverOptions,
+) -> (Vec<Vec<usize>>, Strategy) {
+    let strategy = select_strategy(numbers, target, precision, options);
+
+    let result = match strategy {
+        Strategy::BitEnum => bit_enum::find_subset_sum_bit_enum_raw(numbers, target, precision, find_all),
+        Strategy::MeetMiddle => meet_middle::find_subset_sum_meet_middle_raw(numbers, target, precision, find_all),
+        Strategy::SchroeppelShamir => meet_middle::find_subset_sum_schroeppel_shamir_raw(numbers, target, precision, find_all),
+        Strategy::Dp => dynamic_prog::find_subset_sum_dp_raw(numbers, target, precision, find_all),
+        Strategy::BranchBound => branch_bound::find_subset_sum_branch_bound_raw(numbers, target, precision, find_all),
+        Strategy::Fptas => fptas::find_subset_sum_fptas_raw(numbers, target, precision, options.fptas_epsilon),
+    };
+
+    (result, strategy)
+}
+
+/// 只统计数量、不返回具体下标集合的求解入口，阈值逻辑同`solve_subset_sum`
+pub fn solve_subset_sum_count(numbers: &[i64], target: i64, precision: i64) -> (u64, Strategy) {
+    solve_subset_sum_count_with_options(numbers, target, precision, &SolverOptions::default())
+}
+
+/// 同`solve_subset_sum_count`，允许调用方覆盖自动选择的阈值
+pub fn solve_subset_sum_count_with_options(
+    numbers: &[i64],
+    target: i64,
+    precision: i64,
+    options: &SolverOptions,
+) -> (u64, Strategy) {
+    let strategy = select_strategy(numbers, target, precision, options);
+
+    let count = match strategy {
+        Strategy::BitEnum => bit_enum::find_subset_sum_bit_enum_count_raw(numbers, target, precision),
+        Strategy::MeetMiddle => meet_middle::find_subset_sum_meet_middle_count_raw(numbers, target, precision),
+        Strategy::SchroeppelShamir => {
+            meet_middle::find_subset_sum_schroeppel_shamir_count_raw(numbers, target, precision)
+        }
+        Strategy::Dp => dynamic_prog::find_subset_sum_dp_count_raw(numbers, target, precision),
+        Strategy::BranchBound => branch_bound::find_subset_sum_branch_bound_count_raw(numbers, target, precision),
+        Strategy::Fptas => fptas::find_subset_sum_fptas_count_raw(numbers, target, precision, options.fptas_epsilon),
+    };
+
+    (count, strategy)
+}
+
+/// 根据n、target量级、数值是否全非负选择`Strategy`，供`solve_subset_sum_with_options`
+/// 和`solve_subset_sum_count_with_options`共用
+fn select_strategy(numbers: &[i64], target: i64, precision: i64, options: &SolverOptions) -> Strategy {
+    let n = numbers.len();
+    let all_non_negative = numbers.iter().all(|&x| x >= 0) && target >= 0;
+
+    // target本身的范围不能说明DP表有多宽：find_subset_sum_dp_raw的表宽是
+    // max_sum-min_sum+1，完全由数值本身的量级决定（为支持负数而非target驱动，
+    // 见chunk0-3），一个很大的数字混进很小的target里也会撑爆Vec<Vec<bool>>，
+    // 两个条件都要满足才下放到Dp，否则按n在bit_enum/meet_middle/fptas/branch_bound中选择
+    let target_range_is_small = target.saturating_add(precision).max(0) <= options.dp_max_range;
+    let min_sum: i64 = numbers.iter().filter(|&&x| x < 0).sum();
+    let max_sum: i64 = numbers.iter().filter(|&&x| x > 0).sum();
+    let dp_width = max_sum.saturating_sub(min_sum).saturating_add(1);
+    let dp_width_is_small = dp_width <= options.dp_max_range;
+
+    if all_non_negative && target_range_is_small && dp_width_is_small {
+        Strategy::Dp
+    } else if n <= options.bit_enum_max_n {
+        Strategy::BitEnum
+    } else if n <= options.meet_middle_max_n {
+        Strategy::MeetMiddle
+    } else if all_non_negative && n > options.fptas_min_n {
+        Strategy::Fptas
+    } else {
+        Strategy::BranchBound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_selects_dp_for_small_non_negative_target() {
+        let numbers = vec![1, 2, 3, 4, 5];
+        let (results, strategy) = solve_subset_sum(&numbers, 9, 0, true);
+
+        assert_eq!(strategy, Strategy::Dp);
+        for indices in &results {
+            let sum: i64 = indices.iter().map(|&i| numbers[i]).sum();
+            assert_eq!(sum, 9);
+        }
+    }
+
+    #[test]
+    fn test_auto_selects_branch_bound_for_large_n() {
+        let numbers: Vec<i64> = (1..=45).collect();
+        let (_, strategy) = solve_subset_sum_with_options(
+            &numbers,
+            1_000_000,
+            0,
+            false,
+            &SolverOptions { dp_max_range: 0, ..SolverOptions::default() },
+        );
+
+        assert_eq!(strategy, Strategy::BranchBound);
+    }
+
+    #[test]
+    fn test_auto_avoids_dp_when_numbers_span_is_large_even_if_target_is_small() {
+        // target很小，但一个大数字把DP表宽(max_sum-min_sum)撑到远超dp_max_range，
+        // 不该被"target+precision<=dp_max_range"这一条单独放行进Dp
+        let numbers = vec![50_000_000, 1, 2, 3];
+        let (_, strategy) = solve_subset_sum_with_options(
+            &numbers,
+            6,
+            0,
+            false,
+            &SolverOptions::default(),
+        );
+
+        assert_ne!(strategy, Strategy::Dp);
+    }
+
+    #[test]
+    fn test_auto_selects_fptas_beyond_branch_bound_range() {
+        let numbers: Vec<i64> = (1..=70).collect();
+        let (results, strategy) = solve_subset_sum_with_options(
+            &numbers,
+            1_000_000,
+            0,
+            false,
+            &SolverOptions { dp_max_range: 0, ..SolverOptions::default() },
+        );
+
+        assert_eq!(strategy, Strategy::Fptas);
+        assert!(!results.is_empty());
+    }
+
+    #[test]
+    fn test_solve_subset_sum_count_matches_find_all_len() {
+        let numbers = vec![1, 2, 3, 4, 5];
+        let (results, _) = solve_subset_sum(&numbers, 9, 0, true);
+        let (count, strategy) = solve_subset_sum_count(&numbers, 9, 0);
+
+        assert_eq!(strategy, Strategy::Dp);
+        assert_eq!(count, results.len() as u64);
+    }
+}
@@ -2,12 +2,17 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 /// 使用动态规划算法查找子集和（直接实现，不依赖Python）
-/// 
+///
 /// 适用于整数问题，特别是当目标值较小时
-/// 时间复杂度: O(n*target)
-/// 
+/// 时间复杂度: O(n*(max_sum-min_sum))
+///
+/// 支持负数：和值轴覆盖 `[min_sum, max_sum]`（`min_sum`为所有负数之和，
+/// `max_sum`为所有正数之和），用 `offset = -min_sum` 把它整体平移到
+/// `[0, max_sum-min_sum]`上作为DP表的下标，这样记账场景里借贷同时存在
+/// 的混合正负数据也能走这条路径，而不止是meet-in-middle和branch-bound。
+///
 /// # 参数
-/// * `numbers` - 整数数组
+/// * `numbers` - 整数数组（可以包含负数）
 /// * `target` - 目标和值
 /// * `precision` - 精度（绝对值）
 /// * `find_all` - 是否查找所有解
@@ -19,35 +24,42 @@ pub fn find_subset_sum_dp_raw(
 ) -> Vec<Vec<usize>> {
     let n = numbers.len();
     let results = Arc::new(Mutex::new(Vec::new()));
-    
-    // 创建DP表
-    let mut dp = vec![vec![false; (target as usize) + 1 + (precision as usize)]; n + 1];
-    dp[0][0] = true;
-    
+
+    // 和值轴的范围：所有负数之和 ~ 所有正数之和
+    let min_sum: i64 = numbers.iter().filter(|&&x| x < 0).sum();
+    let max_sum: i64 = numbers.iter().filter(|&&x| x > 0).sum();
+    let offset = -min_sum;
+    let width = (max_sum - min_sum + 1) as usize;
+
+    // 创建DP表，dp[i][(j + offset) as usize] 表示用前i个数字能否凑出和为j
+    let mut dp = vec![vec![false; width]; n + 1];
+    dp[0][offset as usize] = true;
+
     // 填充DP表
     for i in 1..=n {
-        dp[i][0] = true;
-        for j in 0..=target as usize + precision as usize {
+        let val = numbers[i - 1];
+        for j in 0..width {
             // 不选当前元素
             dp[i][j] = dp[i-1][j];
-            
-            // 选当前元素
-            let val = numbers[i-1] as usize;
-            if j >= val {
-                dp[i][j] |= dp[i-1][j - val];
+
+            // 选当前元素：从 (j - offset) - val 对应的下标转移过来
+            let prev = j as i64 - val;
+            if prev >= 0 && (prev as usize) < width {
+                dp[i][j] |= dp[i-1][prev as usize];
             }
         }
     }
-    
+
     // 查找符合目标和的解
     let mut temp_path = vec![false; n];
-    
+
     fn back_track(
-        i: usize, 
-        j: usize, 
-        path: &mut Vec<bool>, 
-        numbers: &[i64], 
-        dp: &Vec<Vec<bool>>, 
+        i: usize,
+        j: i64,
+        offset: i64,
+        path: &mut Vec<bool>,
+        numbers: &[i64],
+        dp: &Vec<Vec<bool>>,
         target: i64,
         precision: i64,
         results: &Arc<Mutex<Vec<Vec<usize>>>>,
@@ -61,12 +73,12 @@ pub fn find_subset_sum_dp_raw(
                 .filter(|(_, &included)| included)
                 .map(|(idx, _)| idx)
                 .collect();
-            
+
             // 计算当前和
             let sum: i64 = indices.iter()
                 .map(|&idx| numbers[idx])
                 .sum();
-            
+
             // 检查是否满足目标和
             let is_valid = if precision == 0 {
                 // 精度为0时要求完全匹配
@@ -75,7 +87,7 @@ pub fn find_subset_sum_dp_raw(
                 // 有精度时允许在范围内
                 (sum - target).abs() <= precision
             };
-            
+
             if is_valid {
                 let mut guard = results.lock().unwrap();
                 guard.push(indices);
@@ -83,63 +95,104 @@ pub fn find_subset_sum_dp_raw(
             }
             return false;
         }
-        
+
         // 不选当前元素的情况
-        if dp[i-1][j] {
+        if dp[i-1][(j + offset) as usize] {
             path[i-1] = false;
-            if back_track(i-1, j, path, numbers, dp, target, precision, results, find_all) {
+            if back_track(i-1, j, offset, path, numbers, dp, target, precision, results, find_all) {
                 return true;
             }
         }
-        
+
         // 选当前元素的情况
-        let val = numbers[i-1] as usize;
-        if j >= val && dp[i-1][j - val] {
+        let val = numbers[i-1];
+        let prev = j - val;
+        if prev + offset >= 0 && (prev + offset) < dp[0].len() as i64 && dp[i-1][(prev + offset) as usize] {
             path[i-1] = true;
-            if back_track(i-1, j - val, path, numbers, dp, target, precision, results, find_all) {
+            if back_track(i-1, prev, offset, path, numbers, dp, target, precision, results, find_all) {
                 return true;
             }
         }
-        
+
         return false;
     }
-    
-    // 查找解集
-    let target_range: Vec<usize> = if precision == 0 {
-        // 精度为0时只查找精确匹配
-        vec![target as usize]
-    } else {
-        // 精度不为0时查找范围内的所有值
-        let lower_bound = (target - precision) as usize;
-        let upper_bound = (target + precision) as usize;
-        (lower_bound..=upper_bound)
-            .filter(|&j| j < dp[0].len())
-            .collect()
-    };
-    
-    for j in target_range {
-        if j < dp[0].len() && dp[n][j] {
-            back_track(n, j, &mut temp_path, numbers, &dp, target, precision, &results, find_all);
-            
-            // 如果不需要找到所有解且已经找到解，则退出
-            if !find_all {
-                let guard = results.lock().unwrap();
-                if !guard.is_empty() {
-                    break;
+
+    // 查找解集：枚举 [target-precision, target+precision] 与 [min_sum, max_sum] 的交集
+    let lower_bound = (target - precision).max(min_sum);
+    let upper_bound = (target + precision).min(max_sum);
+
+    if lower_bound <= upper_bound {
+        for j in lower_bound..=upper_bound {
+            if dp[n][(j + offset) as usize] {
+                back_track(n, j, offset, &mut temp_path, numbers, &dp, target, precision, &results, find_all);
+
+                // 如果不需要找到所有解且已经找到解，则退出
+                if !find_all {
+                    let guard = results.lock().unwrap();
+                    if !guard.is_empty() {
+                        break;
+                    }
                 }
             }
         }
     }
-    
+
     // 返回结果
     let final_results = {
         let guard = results.lock().unwrap();
         guard.clone()
     };
-    
+
     final_results
 }
 
+/// 只统计满足条件的子集数量，不materialize下标向量（直接实现，不依赖Python）
+///
+/// 和`find_subset_sum_dp_raw`共用同一张和值轴，但DP表存的是到达每个和值的
+/// 方案数(`u64`)而不是可达性(`bool`)，省去了按需回溯重建每个下标集合的开销，
+/// 答案就是`[target-precision, target+precision]`窗口内方案数之和。
+///
+/// # 参数
+/// * `numbers` - 整数数组（可以包含负数）
+/// * `target` - 目标和值
+/// * `precision` - 精度（绝对值）
+pub fn find_subset_sum_dp_count_raw(numbers: &[i64], target: i64, precision: i64) -> u64 {
+    let n = numbers.len();
+
+    let min_sum: i64 = numbers.iter().filter(|&&x| x < 0).sum();
+    let max_sum: i64 = numbers.iter().filter(|&&x| x > 0).sum();
+    let offset = -min_sum;
+    let width = (max_sum - min_sum + 1) as usize;
+
+    let mut dp = vec![vec![0u64; width]; n + 1];
+    dp[0][offset as usize] = 1;
+
+    for i in 1..=n {
+        let val = numbers[i - 1];
+        for j in 0..width {
+            let mut ways = dp[i - 1][j];
+
+            let prev = j as i64 - val;
+            if prev >= 0 && (prev as usize) < width {
+                ways += dp[i - 1][prev as usize];
+            }
+
+            dp[i][j] = ways;
+        }
+    }
+
+    let lower_bound = (target - precision).max(min_sum);
+    let upper_bound = (target + precision).min(max_sum);
+
+    if lower_bound > upper_bound {
+        return 0;
+    }
+
+    (lower_bound..=upper_bound)
+        .map(|j| dp[n][(j + offset) as usize])
+        .sum()
+}
+
 /// 收集指定目标范围内的结果
 fn collect_results(
     dp: Vec<bool>,
@@ -251,4 +304,32 @@ mod tests {
             assert_eq!(sum, target);
         }
     }
+
+    #[test]
+    fn test_dp_with_negative_numbers() {
+        // 记账场景：借贷同时存在
+        let numbers = vec![-5, 3, 7, -2, 10];
+        let target = 5;
+        let precision = 0;
+
+        let results = find_subset_sum_dp_raw(&numbers, target, precision, true);
+
+        assert!(!results.is_empty());
+        for indices in &results {
+            let sum: i64 = indices.iter().map(|&i| numbers[i]).sum();
+            assert_eq!(sum, target);
+        }
+    }
+
+    #[test]
+    fn test_dp_count_matches_raw_len() {
+        let numbers = vec![1, 2, 3, 4, 5];
+        let target = 9;
+        let precision = 0;
+
+        let results = find_subset_sum_dp_raw(&numbers, target, precision, true);
+        let count = find_subset_sum_dp_count_raw(&numbers, target, precision);
+
+        assert_eq!(count, results.len() as u64);
+    }
 }
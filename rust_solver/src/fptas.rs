@@ -0,0 +1,308 @@
+use crate::dynamic_prog;
+use std::collections::HashMap;
+
+/// FPTAS逐层展开表中的一条可达和记录及其回溯指针
+#[derive(Clone, Copy)]
+struct Entry {
+    sum: i64,
+    /// 上一层（只看过前`k-1`个数字时）表中的下标
+    parent: usize,
+    /// 相对上一层，本条记录是否选择了当前正在处理的数字
+    included: bool,
+}
+
+/// 精确可达性旁路表中的一条记录：按和值（而不是下标）索引，回溯时直接用
+/// 上一层的`parent_sum`去上一层的`HashMap`里查
+#[derive(Clone, Copy)]
+struct ExactEntry {
+    parent_sum: i64,
+    included: bool,
+}
+
+/// 精确旁路表每层允许追踪的最大候选和数量
+///
+/// 旁路表按`branch_bound`同款的后缀和剪枝限定范围，这个范围宽度最多是
+/// `min(target+precision, numbers之和+2*precision)+1`，取决于数值和`target`/
+/// `precision`的量级而非`n`——当`target`和数值总和都很大时（恰好是`fptas_min_n`
+/// 把调用路由到这里的场景：n和target同时很大），宽度仍可能退化到接近
+/// `target`本身，让这张"旁路"表重新变成O(n*target)的完整DP，吃掉FPTAS本该
+/// 有的多项式时间保证。超过这个预算就放弃精确追踪，退回到只在当前层检查
+/// 的历史行为（仍然是尽力而为，但不再承诺跨层不丢精确命中）。
+const EXACT_TRACKING_BUDGET: i64 = 200_000;
+
+/// 使用FPTAS（全多项式时间近似方案）查找子集和的近似解（直接实现，不依赖Python）
+///
+/// 适用于`branch_bound`也扛不住的超大规模非负整数输入。只保证返回的和与
+/// “不超过target+precision的可达到的最大和”相差不超过相对误差`epsilon`，
+/// 是多项式时间的近似解，不是精确解，也没有`find_all`语义（只返回一个解）。
+///
+/// 经典做法：维护一个有序的可达和列表`L`，初始为`[0]`；每处理一个数字
+/// `x_i`，与`L`中每个和相加得到候选和的并集并排序，丢弃超出
+/// `target+precision`的候选，再*裁剪*：升序扫描，仅当某个和`y`比上一个
+/// 保留的和`z`大出`(1+epsilon/n)`倍以上才保留，这样`|L|`被限制在
+/// `O((n/epsilon)*log(target))`。最终答案是最后一层里最大的和，沿着每层
+/// 记录的回溯指针重建出选中的下标集合。
+///
+/// 只在当前层判断"是否裁剪掉了命中`[target-precision, target+precision]`的
+/// 和"不足以保证不漏掉精确解：一个中间和可能在更早的层被裁剪掉，而它本该
+/// 在加上后面的数字后落进目标窗口。因此单独维护一张不做近似裁剪的精确可达
+/// 性旁路表`exact_layers`，每层只保留"配合剩余数字的后缀和还有可能落进
+/// `[target-precision, target+precision]`"的和（用`branch_bound`同款的后缀和
+/// 剪枝限定范围），最终层里只要存在落在窗口内的和，就优先于近似解返回它。
+///
+/// 这张旁路表的宽度由数值总和与`target`/`precision`的量级决定，在`target`
+/// 和数值总和都很大时可能退化成接近完整DP的规模——见`EXACT_TRACKING_BUDGET`。超过预算
+/// 时放弃跨层精确追踪，保留裁剪前历史就有的单层检查（尽力而为，不再是严格
+/// 保证），换取FPTAS本该有的多项式时间复杂度。
+///
+/// # 参数
+/// * `numbers` - 非负整数数组（不支持负数，调用方需自行保证；出现负数时
+///   直接返回空结果）
+/// * `target` - 目标和值（上界）
+/// * `precision` - 允许的绝对误差，和`target`一起构成可达和的上限`target+precision`
+/// * `epsilon` - 相对误差，`epsilon<=0`时退化为精确DP(`find_subset_sum_dp_raw`)
+pub fn find_subset_sum_fptas_raw(
+    numbers: &[i64],
+    target: i64,
+    precision: i64,
+    epsilon: f64,
+) -> Vec<Vec<usize>> {
+    if numbers.iter().any(|&x| x < 0) {
+        return Vec::new();
+    }
+
+    if epsilon <= 0.0 {
+        return dynamic_prog::find_subset_sum_dp_raw(numbers, target, precision, false);
+    }
+
+    let n = numbers.len();
+    let cap = target.saturating_add(precision);
+    let lower = (target - precision).max(0);
+
+    // 后缀和：suffix_sum[k] = numbers[k..]之和，用来判断某个中间和配合剩余
+    // 数字还有没有可能落进[lower, cap]——和branch_bound.rs里suffix_pos的
+    // 剪枝思路一致，避免exact_layers退化成无界的完整DP
+    let mut suffix_sum = vec![0i64; n + 1];
+    for i in (0..n).rev() {
+        suffix_sum[i] = suffix_sum[i + 1] + numbers[i];
+    }
+
+    // 精确旁路表每层可能追踪的和值范围是[max(0, lower-suffix_sum[k]), cap]，
+    // 宽度取决于target/precision和剩余数字之和的量级；只有当它始终不超过预算
+    // 时才值得去建这张表，否则它会退化成O(n*target)的完整DP
+    let max_window_width = (0..=n)
+        .map(|k| {
+            let lo = (lower - suffix_sum[k]).max(0);
+            if lo > cap { 0 } else { cap - lo + 1 }
+        })
+        .max()
+        .unwrap_or(0);
+    let track_exact = max_window_width <= EXACT_TRACKING_BUDGET;
+
+    // 第0层：只有和为0的空集，parent字段在第0层不会被用到
+    let mut layers: Vec<Vec<Entry>> = vec![vec![Entry { sum: 0, parent: 0, included: false }]];
+
+    // 精确可达性旁路表，按层存放，独立于上面近似裁剪的`layers`，保证
+    // "[lower, cap]窗口内有精确解就一定能找到"这个承诺不被裁剪打破
+    // （超出EXACT_TRACKING_BUDGET时不构建，见上）
+    let mut exact_layers: Vec<HashMap<i64, ExactEntry>> = vec![HashMap::from([(0, ExactEntry { parent_sum: 0, included: false })])];
+
+    for (k, &x) in numbers.iter().enumerate() {
+        let prev = layers.last().unwrap();
+        let mut merged: Vec<Entry> = Vec::with_capacity(prev.len() * 2);
+        for (idx, e) in prev.iter().enumerate() {
+            merged.push(Entry { sum: e.sum, parent: idx, included: false });
+            let with_item = e.sum + x;
+            if with_item <= cap {
+                merged.push(Entry { sum: with_item, parent: idx, included: true });
+            }
+        }
+        merged.sort_by_key(|e| e.sum);
+
+        // 命中[lower, cap]区间的候选：裁剪后必须至少留下一个，避免漏掉精确解
+        let exact_hit = merged.iter().find(|e| e.sum >= lower && e.sum <= cap).copied();
+
+        let mut trimmed: Vec<Entry> = Vec::with_capacity(merged.len());
+        for e in merged {
+            let keep = match trimmed.last() {
+                None => true,
+                Some(last) => (e.sum as f64) > (last.sum as f64) * (1.0 + epsilon / n as f64),
+            };
+            if keep {
+                trimmed.push(e);
+            }
+        }
+
+        if let Some(hit) = exact_hit {
+            if !trimmed.iter().any(|e| e.sum >= lower && e.sum <= cap) {
+                let pos = trimmed.partition_point(|e| e.sum < hit.sum);
+                trimmed.insert(pos, hit);
+            }
+        }
+
+        layers.push(trimmed);
+
+        if track_exact {
+            // 精确旁路表的这一层：对每个可能的和只保留一条记录（不裁剪），
+            // 但只收`remaining = suffix_sum[k+1]`（处理完numbers[k]后还剩的
+            // 数字之和）配合进去后仍有可能落进[lower, cap]的和——即
+            // `sum <= cap`且`sum + remaining >= lower`，否则这个和不管怎么
+            // 加都到不了窗口，不值得继续带着走
+            let remaining = suffix_sum[k + 1];
+            let prev_exact = exact_layers.last().unwrap();
+            let mut next_exact: HashMap<i64, ExactEntry> = HashMap::new();
+            for (&sum, _) in prev_exact.iter() {
+                // 不选当前数字
+                if sum <= cap && sum.saturating_add(remaining) >= lower {
+                    next_exact.entry(sum).or_insert(ExactEntry { parent_sum: sum, included: false });
+                }
+                // 选当前数字
+                let with_item = sum + x;
+                if with_item <= cap && with_item.saturating_add(remaining) >= lower {
+                    next_exact.entry(with_item).or_insert(ExactEntry { parent_sum: sum, included: true });
+                }
+            }
+            exact_layers.push(next_exact);
+        }
+    }
+
+    // 精确旁路表的最后一层里，只要有和落在[lower, cap]窗口内就说明存在精确
+    // 解（或满足precision的解），必须优先于近似解返回，否则就是"文档说不会
+    // 丢精确命中，但裁剪确实丢了"（未建表时直接跳过，退回只靠近似解）
+    let exact_best = if track_exact {
+        exact_layers
+            .last()
+            .unwrap()
+            .iter()
+            .filter(|(&sum, _)| sum >= lower && sum <= cap)
+            .max_by_key(|(&sum, _)| sum)
+            .map(|(&sum, _)| sum)
+    } else {
+        None
+    };
+
+    let mut indices = Vec::new();
+
+    if let Some(target_sum) = exact_best {
+        // 沿exact_layers的回溯指针从最后一层走回第0层
+        let mut sum = target_sum;
+        let mut k = n;
+        while k > 0 {
+            let entry = exact_layers[k][&sum];
+            if entry.included {
+                indices.push(k - 1);
+            }
+            sum = entry.parent_sum;
+            k -= 1;
+        }
+        indices.reverse();
+        return vec![indices];
+    }
+
+    let best = match layers.last().unwrap().iter().filter(|e| e.sum <= cap).max_by_key(|e| e.sum) {
+        Some(e) => *e,
+        None => return Vec::new(),
+    };
+
+    // 沿回溯指针从最后一层走回第0层，重建选中的下标集合
+    let mut entry = best;
+    let mut k = n;
+    while k > 0 {
+        if entry.included {
+            indices.push(k - 1);
+        }
+        entry = layers[k - 1][entry.parent];
+        k -= 1;
+    }
+    indices.reverse();
+
+    vec![indices]
+}
+
+/// 统计FPTAS近似解的数量（只会是0或1，没有`find_all`语义下的计数意义）
+///
+/// FPTAS本身只返回一个近似最优解，不枚举所有可行子集，这里如实返回
+/// "是否找到了一个满足约束的近似解"，不假装有精确计数能力。
+///
+/// # 参数
+/// * `numbers` - 非负整数数组
+/// * `target` - 目标和值（上界）
+/// * `precision` - 允许的绝对误差
+/// * `epsilon` - 相对误差，语义同`find_subset_sum_fptas_raw`
+pub fn find_subset_sum_fptas_count_raw(numbers: &[i64], target: i64, precision: i64, epsilon: f64) -> u64 {
+    find_subset_sum_fptas_raw(numbers, target, precision, epsilon).len() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fptas_approximates_target() {
+        let numbers = vec![10, 20, 30, 40, 50, 60, 70];
+        let target = 100;
+        let precision = 0;
+        let epsilon = 0.1;
+
+        let results = find_subset_sum_fptas_raw(&numbers, target, precision, epsilon);
+
+        assert_eq!(results.len(), 1);
+        let sum: i64 = results[0].iter().map(|&i| numbers[i]).sum();
+        assert!(sum <= target);
+        // 误差应在相对误差epsilon之内
+        assert!((target - sum) as f64 <= epsilon * target as f64 + 1.0);
+    }
+
+    #[test]
+    fn test_fptas_zero_epsilon_falls_back_to_exact() {
+        let numbers = vec![1, 2, 3, 4, 5];
+        let target = 9;
+
+        let results = find_subset_sum_fptas_raw(&numbers, target, 0, 0.0);
+
+        assert_eq!(results.len(), 1);
+        let sum: i64 = results[0].iter().map(|&i| numbers[i]).sum();
+        assert_eq!(sum, target);
+    }
+
+    #[test]
+    fn test_fptas_never_drops_an_exact_hit_behind_a_trimmed_precursor() {
+        // 精确解的路径是20+22+1+4=47，但在处理到x=1时，能通向47的中间和43
+        // 恰好落在裁剪窗口之外被丢弃；exact_layers这张旁路表必须独立于裁剪
+        // 捕到这条路径，否则FPTAS会退而求其次返回46
+        let numbers = vec![20, 22, 1, 4];
+        let target = 47;
+        let precision = 0;
+        let epsilon = 0.1;
+
+        let results = find_subset_sum_fptas_raw(&numbers, target, precision, epsilon);
+
+        assert_eq!(results.len(), 1);
+        let sum: i64 = results[0].iter().map(|&i| numbers[i]).sum();
+        assert_eq!(sum, target);
+    }
+
+    #[test]
+    fn test_fptas_skips_exact_tracking_beyond_budget_without_hanging() {
+        // target和数值总和都远超EXACT_TRACKING_BUDGET时（这里第0层窗口宽度
+        // 为900004），跨层精确旁路表会被跳过，退回纯近似裁剪；这里只断言它仍能
+        // 在合理时间内返回一个不超过target的结果，不会退化成O(n*target)的完整DP
+        let numbers = vec![400_000, 300_000, 200_000, 1, 2];
+        let target = 1_000_000;
+        let precision = 0;
+        let epsilon = 0.1;
+
+        let results = find_subset_sum_fptas_raw(&numbers, target, precision, epsilon);
+
+        assert_eq!(results.len(), 1);
+        let sum: i64 = results[0].iter().map(|&i| numbers[i]).sum();
+        assert!(sum <= target);
+    }
+
+    #[test]
+    fn test_fptas_rejects_negative_numbers() {
+        let numbers = vec![-1, 2, 3];
+        let results = find_subset_sum_fptas_raw(&numbers, 4, 0, 0.1);
+        assert!(results.is_empty());
+    }
+}
@@ -7,101 +7,228 @@ mod bit_enum;
 mod meet_middle;
 mod dynamic_prog;
 mod branch_bound;
+mod decimal;
+mod dispatch;
+mod fptas;
+
+pub use decimal::solve_subset_sum_decimal;
+pub use dispatch::{solve_subset_sum, solve_subset_sum_with_options, SolverOptions, Strategy};
 
 /// 查找子集和
-/// 
+///
 /// 根据数据规模自动选择最合适的算法
-/// 
+///
 /// C ABI接口，可以被Python通过cffi调用
+///
+/// `decimals`指定输入数据的十进制精度，放大系数为`10^decimals`（例如
+/// decimals=2对应之前硬编码的×100），按四舍五入而不是直接截断转换为定点
+/// 整数——之前固定乘以100再`as i64`截断，超过两位小数的输入（如1.005）会被
+/// 悄悄舍入、可能产生错误的匹配结果或漏掉本该成立的解，这对账务对账场景
+/// 是实打实的正确性问题。调用方若已经持有精确的整数数据（如“分”这样的
+/// 最小计量单位），应改用`rust_find_subset_sum_i64`完全跳过浮点转换。
+///
+/// `count_only`非0时，只统计命中目标的子集数量，写入`*count_ptr`，完全跳过
+/// 下标集合的构建和分配（`result_ptr`/`result_rows`/`result_cols`会被置为
+/// 空/0，不需要调用`rust_free_result`释放，对这些空指针调用它也只是空操作）；这条路径是为"只要知道有
+/// 多少个子集满足条件"的调用方准备的，避免`find_all`在稠密输入上因
+/// materialize海量`Vec<usize>`而OOM。
+///
+/// 当`scale * max(|numbers|) * numbers.len()`或`scale * max(|target|, |precision|)`
+/// 超出`i64`可表示范围时返回`-5`——和`solve_subset_sum_decimal`共用同一个
+/// `scaled_values_may_overflow`判断，避免放大后静默溢出产生错误的匹配结果；
+/// 调用方若需要更大数值范围，应改用`rust_find_subset_sum_i64`。
 #[no_mangle]
 pub extern "C" fn rust_find_subset_sum(
     numbers_ptr: *const c_double,
     numbers_len: c_uint,
     target: c_double,
     precision: c_double,
+    decimals: c_uint,
     find_all: c_int,
     algorithm_ptr: *const u8,
     algorithm_len: c_uint,
+    count_only: c_int,
+    count_ptr: *mut u64,
     result_ptr: *mut *mut c_uint,
     result_rows: *mut c_uint,
     result_cols: *mut *mut c_uint,
 ) -> c_int {
     // 安全检查
-    if numbers_ptr.is_null() || algorithm_ptr.is_null() || result_ptr.is_null() || 
+    if numbers_ptr.is_null() || algorithm_ptr.is_null() || result_ptr.is_null() ||
         result_rows.is_null() || result_cols.is_null() {
         return -1;
     }
-    
+
+    let count_only_bool = count_only != 0;
+    if count_only_bool && count_ptr.is_null() {
+        return -1;
+    }
+
     // 将C数据转换为Rust数据
     let numbers = unsafe {
         slice::from_raw_parts(numbers_ptr, numbers_len as usize)
     };
-    
+
     // 转换算法名称
     let algorithm_bytes = unsafe {
         slice::from_raw_parts(algorithm_ptr, algorithm_len as usize)
     };
-    
+
     let algorithm = match std::str::from_utf8(algorithm_bytes) {
         Ok(s) => s,
         Err(_) => return -2,
     };
-    
-    // 将浮点数转换为整数以提高精度和性能
-    let scale = 100.0; // 放大系数
-    let int_numbers: Vec<i64> = numbers.iter().map(|&x| (x * scale) as i64).collect();
-    let int_target = (target * scale) as i64;
-    let int_precision = (precision * scale) as i64;
-    
+
+    // 溢出保护：和solve_subset_sum_decimal共用同一条判断，否则放大后静默
+    // 溢出会悄悄产生错误的匹配结果
+    let scale = 10f64.powi(decimals as i32);
+    if decimal::scaled_values_may_overflow(numbers, target, precision, scale) {
+        return -5;
+    }
+
+    // 按decimals四舍五入放大为定点整数，而不是直接截断，避免精度丢失导致误判
+    let int_numbers: Vec<i64> = numbers.iter().map(|&x| (x * scale).round() as i64).collect();
+    let int_target = (target * scale).round() as i64;
+    let int_precision = (precision * scale).round() as i64;
+
     // 标记是否查找所有解
     let find_all_bool = find_all != 0;
-    
-    // 根据算法选择合适的实现
-    let result = match algorithm {
-        "bit_enum" => bit_enum::find_subset_sum_bit_enum_raw(&int_numbers, int_target, int_precision, find_all_bool),
-        "meet_middle" => meet_middle::find_subset_sum_meet_middle_raw(&int_numbers, int_target, int_precision, find_all_bool),
-        "dp" => dynamic_prog::find_subset_sum_dp_raw(&int_numbers, int_target, int_precision, find_all_bool),
-        "branch_bound" => branch_bound::find_subset_sum_branch_bound_raw(&int_numbers, int_target, int_precision, find_all_bool),
-        "auto" | _ => {
-            // 根据数据规模自动选择算法
-            let n = int_numbers.len();
-            if n <= 25 {
-                bit_enum::find_subset_sum_bit_enum_raw(&int_numbers, int_target, int_precision, find_all_bool)
-            } else if n <= 40 {
-                meet_middle::find_subset_sum_meet_middle_raw(&int_numbers, int_target, int_precision, find_all_bool)
-            } else {
-                branch_bound::find_subset_sum_branch_bound_raw(&int_numbers, int_target, int_precision, find_all_bool)
-            }
-        }
+
+    dispatch_and_emit(
+        &int_numbers,
+        int_target,
+        int_precision,
+        find_all_bool,
+        algorithm,
+        count_only_bool,
+        count_ptr,
+        result_ptr,
+        result_rows,
+        result_cols,
+    )
+}
+
+/// 查找子集和（整数原生入口，不经过浮点转换）
+///
+/// `numbers`/`target`/`precision`直接是已经是定点整数（如“分”这样的最小
+/// 计量单位）的调用方，可以用这个入口完全绕开`rust_find_subset_sum`里
+/// 按`decimals`放大、四舍五入的浮点转换路径，不会有任何精度损失。两者最终
+/// 都走同一套`dispatch_algorithm`/`dispatch_algorithm_count`分派，参数含义
+/// （`find_all`/`algorithm`/`count_only`等）和`rust_find_subset_sum`一致。
+///
+/// C ABI接口，可以被Python通过cffi调用
+#[no_mangle]
+pub extern "C" fn rust_find_subset_sum_i64(
+    numbers_ptr: *const i64,
+    numbers_len: c_uint,
+    target: i64,
+    precision: i64,
+    find_all: c_int,
+    algorithm_ptr: *const u8,
+    algorithm_len: c_uint,
+    count_only: c_int,
+    count_ptr: *mut u64,
+    result_ptr: *mut *mut c_uint,
+    result_rows: *mut c_uint,
+    result_cols: *mut *mut c_uint,
+) -> c_int {
+    // 安全检查
+    if numbers_ptr.is_null() || algorithm_ptr.is_null() || result_ptr.is_null() ||
+        result_rows.is_null() || result_cols.is_null() {
+        return -1;
+    }
+
+    let count_only_bool = count_only != 0;
+    if count_only_bool && count_ptr.is_null() {
+        return -1;
+    }
+
+    // 将C数据转换为Rust数据，已经是整数，不需要任何缩放
+    let numbers = unsafe {
+        slice::from_raw_parts(numbers_ptr, numbers_len as usize)
     };
-    
+
+    // 转换算法名称
+    let algorithm_bytes = unsafe {
+        slice::from_raw_parts(algorithm_ptr, algorithm_len as usize)
+    };
+
+    let algorithm = match std::str::from_utf8(algorithm_bytes) {
+        Ok(s) => s,
+        Err(_) => return -2,
+    };
+
+    let find_all_bool = find_all != 0;
+
+    dispatch_and_emit(
+        numbers,
+        target,
+        precision,
+        find_all_bool,
+        algorithm,
+        count_only_bool,
+        count_ptr,
+        result_ptr,
+        result_rows,
+        result_cols,
+    )
+}
+
+/// 分派到具体求解器并把结果写入C ABI的输出参数，供`rust_find_subset_sum`和
+/// `rust_find_subset_sum_i64`共用
+fn dispatch_and_emit(
+    numbers: &[i64],
+    target: i64,
+    precision: i64,
+    find_all: bool,
+    algorithm: &str,
+    count_only: bool,
+    count_ptr: *mut u64,
+    result_ptr: *mut *mut c_uint,
+    result_rows: *mut c_uint,
+    result_cols: *mut *mut c_uint,
+) -> c_int {
+    if count_only {
+        let count = dispatch::dispatch_algorithm_count(numbers, target, precision, algorithm);
+        unsafe {
+            *count_ptr = count;
+            *result_ptr = std::ptr::null_mut();
+            *result_rows = 0;
+            *result_cols = std::ptr::null_mut();
+        }
+        return 0;
+    }
+
+    // 根据算法名称分派到具体实现，"auto"时按规模自动选择
+    let result = dispatch::dispatch_algorithm(numbers, target, precision, find_all, algorithm);
+
     // 将结果转换为C可用的格式
     let (rows, cols_vec, flat_data) = convert_result_to_c_format(&result);
-    
+
     unsafe {
         // 为结果分配内存并复制数据
         let flat_data_ptr = libc::malloc(flat_data.len() * std::mem::size_of::<c_uint>()) as *mut c_uint;
         if flat_data_ptr.is_null() {
             return -3;
         }
-        
+
         std::ptr::copy_nonoverlapping(flat_data.as_ptr(), flat_data_ptr, flat_data.len());
-        
+
         // 为列长度分配内存并复制数据
         let cols_ptr = libc::malloc(cols_vec.len() * std::mem::size_of::<c_uint>()) as *mut c_uint;
         if cols_ptr.is_null() {
             libc::free(flat_data_ptr as *mut libc::c_void);
             return -4;
         }
-        
+
         std::ptr::copy_nonoverlapping(cols_vec.as_ptr(), cols_ptr, cols_vec.len());
-        
+
         // 设置输出参数
         *result_ptr = flat_data_ptr;
         *result_rows = rows;
         *result_cols = cols_ptr;
     }
-    
+
     0  // 成功返回0
 }
 
@@ -123,7 +250,7 @@ fn convert_result_to_c_format(result: &Vec<Vec<usize>>) -> (c_uint, Vec<c_uint>,
     (rows, cols_vec, flat_data)
 }
 
-/// 释放由rust_find_subset_sum分配的内存
+/// 释放由rust_find_subset_sum/rust_find_subset_sum_i64分配的内存
 #[no_mangle]
 pub extern "C" fn rust_free_result(
     data_ptr: *mut c_uint,
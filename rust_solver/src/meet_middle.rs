@@ -1,5 +1,7 @@
 use rayon::prelude::*;
 use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::{Arc, Mutex};
 
 /// 使用Meet-in-the-Middle算法查找子集和（直接实现，不依赖Python）
@@ -20,9 +22,10 @@ pub fn find_subset_sum_meet_middle_raw(
 ) -> Vec<Vec<usize>> {
     let results = Arc::new(Mutex::new(Vec::new()));
     let results_for_closure = results.clone();
-    let found = Arc::new(Mutex::new(false));
-    let found_for_closure = found.clone();
-    
+    // 共享的取消标志：只需要一个解时，一旦某个block命中，通知其它block尽快停止
+    let cancel = Arc::new(AtomicBool::new(false));
+    let cancel_for_closure = cancel.clone();
+
     let n = numbers.len();
     let mid = n / 2;
     
@@ -57,7 +60,7 @@ pub fn find_subset_sum_meet_middle_raw(
         
         for mask in start..end {
             // 如果只需要找到一个解且已经找到，则提前退出
-            if !find_all && *found_for_closure.lock().unwrap() {
+            if !find_all && cancel_for_closure.load(AtomicOrdering::Acquire) {
                 break;
             }
             
@@ -79,35 +82,62 @@ pub fn find_subset_sum_meet_middle_raw(
             let start_pos = binary_search_lower_bound(&first_half, lower_bound);
             
             for i in start_pos..first_half.len() {
+                // 内层循环同样要看cancel，否则多个block各自跑完自己的区间后
+                // 才在下一次mask迭代时观察到标志，!find_all时可能已经各push了一个解
+                if !find_all && cancel_for_closure.load(AtomicOrdering::Acquire) {
+                    break;
+                }
+
                 let (sum1, indices1) = &first_half[i];
-                
+
                 if *sum1 > upper_bound {
                     break;
                 }
-                
+
                 // 合并两个子集的索引
                 let mut combined_indices = indices1.clone();
                 combined_indices.extend_from_slice(&indices);
-                
+
                 // 添加到结果中
                 let mut results_guard = results_for_closure.lock().unwrap();
                 results_guard.push(combined_indices);
-                
+
                 if !find_all {
-                    let mut found_guard = found_for_closure.lock().unwrap();
-                    *found_guard = true;
+                    cancel_for_closure.store(true, AtomicOrdering::Release);
                     break;
                 }
             }
         }
     });
-    
+
     // 返回结果 - 修改此部分以避免try_unwrap导致的线程恐慌
     let final_results = {
         let guard = results.lock().unwrap();
         guard.clone()  // 直接克隆锁内的数据，而不是尝试unwrap Arc
     };
-    
+
+    // find_all时，折半枚举的两侧block理论上不会产生重复组合，
+    // 但规范化（排序）+去重可以防止未来拆分方式变化后出现重复解
+    let final_results = if find_all {
+        let mut seen = HashSet::new();
+        let mut deduped = Vec::with_capacity(final_results.len());
+        for mut indices in final_results {
+            indices.sort_unstable();
+            if seen.insert(indices.clone()) {
+                deduped.push(indices);
+            }
+        }
+        deduped
+    } else {
+        final_results
+    };
+
+    // 即便加了内层cancel检查，不同block之间仍有竞态窗口：!find_all时如果
+    // 还是收到了多个解，和branch_bound.rs/bit_enum.rs一样只返回第一个
+    if !find_all && final_results.len() > 1 {
+        return vec![final_results[0].clone()];
+    }
+
     final_results
 }
 
@@ -225,6 +255,114 @@ pub fn find_subset_sum_meet_middle(
 }
 */
 
+/// 只统计满足条件的子集数量，不materialize下标向量（直接实现，不依赖Python）
+///
+/// 对左半部分排序后，右半部分的每个和只需要在左半部分里二分查找出
+/// `[target-precision, target+precision]`对应的窗口，用窗口宽度直接累加数量，
+/// 不用把每一对组合都拼出来、推进`Vec<usize>`——整体仍是`O(2^(n/2) log)`，
+/// 但避免了`find_subset_sum_meet_middle_raw`里枚举配对、分配索引向量的开销。
+///
+/// # 参数
+/// * `numbers` - 整数数组
+/// * `target` - 目标和值
+/// * `precision` - 精度（绝对值）
+pub fn find_subset_sum_meet_middle_count_raw(numbers: &[i64], target: i64, precision: i64) -> u64 {
+    let n = numbers.len();
+    let mid = n / 2;
+
+    let mut first_half: Vec<i64> = Vec::with_capacity(1 << mid);
+    for mask in 0..(1 << mid) {
+        let mut sum = 0;
+        for i in 0..mid {
+            if (mask & (1 << i)) != 0 {
+                sum += numbers[i];
+            }
+        }
+        first_half.push(sum);
+    }
+    first_half.sort_unstable();
+
+    let second_half_len = n - mid;
+    let max_second_half = 1u64 << second_half_len;
+
+    let num_cpus = num_cpus::get() as u64;
+    let block_size = (max_second_half / num_cpus) + 1;
+
+    (0..num_cpus)
+        .into_par_iter()
+        .map(|cpu_id| {
+            let start = cpu_id * block_size;
+            let end = std::cmp::min(start + block_size, max_second_half);
+            let mut local_count: u64 = 0;
+
+            for mask in start..end {
+                let mut sum = 0i64;
+                for i in 0..second_half_len {
+                    if (mask & (1 << i)) != 0 {
+                        sum += numbers[mid + i];
+                    }
+                }
+
+                let target_sum = target - sum;
+                let lower_bound = target_sum - precision;
+                let upper_bound = target_sum + precision;
+
+                let start_pos = count_lower_bound_idx(&first_half, lower_bound);
+                let end_pos = count_upper_bound_idx(&first_half, upper_bound);
+                if end_pos > start_pos {
+                    local_count += (end_pos - start_pos) as u64;
+                }
+            }
+
+            local_count
+        })
+        .sum()
+}
+
+/// `arr`（已升序排序）中第一个`>= target`的下标
+fn count_lower_bound_idx(arr: &[i64], target: i64) -> usize {
+    let mut left = 0;
+    let mut right = arr.len();
+    while left < right {
+        let mid = left + (right - left) / 2;
+        if arr[mid] < target {
+            left = mid + 1;
+        } else {
+            right = mid;
+        }
+    }
+    left
+}
+
+/// `arr`（已升序排序）中第一个`> target`的下标
+fn count_upper_bound_idx(arr: &[i64], target: i64) -> usize {
+    let mut left = 0;
+    let mut right = arr.len();
+    while left < right {
+        let mid = left + (right - left) / 2;
+        if arr[mid] <= target {
+            left = mid + 1;
+        } else {
+            right = mid;
+        }
+    }
+    left
+}
+
+/// 使用Schroeppel-Shamir四路分割算法统计满足条件的子集数量
+///
+/// Schroeppel-Shamir暂时没有专门的计数内核，直接复用`find_all=true`的完整
+/// 搜索路径统计数量；四路分割本身已经把峰值内存压到`O(2^(n/4))`，计数场景
+/// 收益不如`meet_middle`/`bit_enum`那样明显，先保持实现简单。
+///
+/// # 参数
+/// * `numbers` - 整数数组
+/// * `target` - 目标和值
+/// * `precision` - 精度（绝对值）
+pub fn find_subset_sum_schroeppel_shamir_count_raw(numbers: &[i64], target: i64, precision: i64) -> u64 {
+    find_subset_sum_schroeppel_shamir_raw(numbers, target, precision, true).len() as u64
+}
+
 /// 二分查找下界
 fn binary_search_lower_bound(arr: &[(i64, Vec<usize>)], target: i64) -> usize {
     let mut left = 0;
@@ -241,10 +379,226 @@ fn binary_search_lower_bound(arr: &[(i64, Vec<usize>)], target: i64) -> usize {
     left
 }
 
+/// 使用Schroeppel-Shamir四路分割算法查找子集和
+///
+/// 标准折半枚举需要枚举 `2^(n/2)` 个子集和并整体排序，内存在 n≈40 附近就耗尽。
+/// 这里把数组四等分为 A、B、C、D（各 `n/4` 个元素），只枚举四份各自的 `2^(n/4)`
+/// 个子集和：A⊕B 通过`AscendingPairStream`按和值从小到大惰性生成，C⊕D 通过
+/// 对称的`DescendingPairStream`按和值从大到小惰性生成（两者都只维护一个大小
+/// `O(2^(n/4))`的堆，不materialize全部 `2^(n/2)` 种组合）。
+///
+/// 两条流用双指针合并：A⊕B 递增时，它对应的有效C⊕D窗口`[lower-ab, upper-ab]`
+/// 只会整体往下滑，和C⊕D的递减方向天然匹配——用一个双端队列`cd_buffer`缓存
+/// 已从C⊕D流取出、但还没被当前或更早的A⊕B消费完的候选：队首（最大）一旦
+/// 比当前窗口上界还大就永久丢弃（A⊕B只会变大，窗口只会继续下滑，不会再用
+/// 到），队尾不够覆盖窗口下界时才继续从流里拉取更小的值。这样总拉取/丢弃
+/// 次数有界（不超过C⊕D流本身的长度），单次A⊕B的匹配开销只正比于它实际命中
+/// 的组合数，避免了对每个A⊕B都重新扫一遍`sums_c`再二分`sums_d`的`O(2^(n/4))`
+/// 开销。`cd_buffer`里同时存活的候选数等于当前滑动窗口`[cd_lower, cd_upper]`
+/// （宽度`2*precision`）命中的C⊕D组合数——`precision`相对C⊕D取值范围较小
+/// （精确匹配、或近似匹配但容差远小于总和量级）时这个数量和堆本身一样是
+/// `O(2^(n/4))`量级，可以把可处理的 n 推到 50 左右；`precision`大到覆盖了
+/// C⊕D绝大部分取值范围时，窗口本身就很宽，缓冲区会随之变大——但这种情况下
+/// 命中的组合数（也就是要返回的`results`本身）同样是那么大，并不是这个实现
+/// 额外引入的开销。
+///
+/// # 参数
+/// * `numbers` - 整数数组
+/// * `target` - 目标和值
+/// * `precision` - 精度（绝对值）
+/// * `find_all` - 是否查找所有解
+pub fn find_subset_sum_schroeppel_shamir_raw(
+    numbers: &[i64],
+    target: i64,
+    precision: i64,
+    find_all: bool,
+) -> Vec<Vec<usize>> {
+    let n = numbers.len();
+    let q = n / 4;
+
+    let group_a: Vec<usize> = (0..q).collect();
+    let group_b: Vec<usize> = (q..2 * q).collect();
+    let group_c: Vec<usize> = (2 * q..3 * q).collect();
+    let group_d: Vec<usize> = (3 * q..n).collect();
+
+    let sums_a = all_subset_sums(numbers, &group_a);
+    let mut sums_b = all_subset_sums(numbers, &group_b);
+    let sums_c = all_subset_sums(numbers, &group_c);
+    let mut sums_d = all_subset_sums(numbers, &group_d);
+
+    sums_b.sort_by_key(|e| e.0);
+    sums_d.sort_by_key(|e| e.0);
+
+    let lower_bound = target - precision;
+    let upper_bound = target + precision;
+
+    // C⊕D 能达到的最小和值：负数输入时可能是负的，所以 A⊕B 即便已经超过
+    // upper_bound，仍可能靠一个很负的 C⊕D 凑回区间，不能只看 ab_sum 本身来
+    // 判断是否还有解。sums_d 已排序，首项即最小值；sums_c 未排序，需要扫一遍
+    let min_c = sums_c.iter().map(|&(s, _)| s).min().unwrap_or(0);
+    let min_d = sums_d.first().map(|&(s, _)| s).unwrap_or(0);
+    let cd_min = min_c + min_d;
+
+    let mut results = Vec::new();
+    let mut ab_stream = AscendingPairStream::new(&sums_a, &sums_b);
+    let mut cd_stream = DescendingPairStream::new(&sums_c, &sums_d);
+
+    // 按和值从大到小缓存的C⊕D候选，随A⊕B递增整体往下滑
+    let mut cd_buffer: std::collections::VecDeque<(i64, Vec<usize>)> = std::collections::VecDeque::new();
+    let mut cd_exhausted = false;
+
+    while let Some((ab_sum, ab_indices)) = ab_stream.next() {
+        if ab_sum + cd_min > upper_bound {
+            // A⊕B 的和只会越来越大，即使配上最小的C⊕D也够不到区间了
+            break;
+        }
+
+        let cd_lower = lower_bound - ab_sum;
+        let cd_upper = upper_bound - ab_sum;
+
+        // 队首比当前窗口上界还大：A⊕B只会继续变大、窗口只会继续下滑，
+        // 这些候选以后也用不上了，永久丢弃
+        while cd_buffer.front().is_some_and(|&(s, _)| s > cd_upper) {
+            cd_buffer.pop_front();
+        }
+
+        // 队尾还没跌破窗口下界就继续从流里拉取更小的候选，保证这一轮
+        // [cd_lower, cd_upper]内的组合全部已经在缓冲区里。缓冲区为空时拉到的
+        // 第一批候选可能本身就比cd_upper还大（比如本轮窗口比上一轮更窄），
+        // 不能假定只要新拉取的就落在窗口内；条件必须是`>= cd_lower`而不是
+        // `> cd_lower`——C⊕D流里可能有多个和值恰好等于cd_lower的组合，一见
+        // 队尾等于cd_lower就停会漏掉排在它后面、和值同样等于cd_lower的组合，
+        // 必须一直拉到严格小于cd_lower才能确认同值的都已经在缓冲区里了
+        while !cd_exhausted && cd_buffer.back().is_none_or(|&(s, _)| s >= cd_lower) {
+            match cd_stream.next() {
+                Some(entry) => cd_buffer.push_back(entry),
+                None => cd_exhausted = true,
+            }
+        }
+
+        // 拉取补充后，队首仍可能比cd_upper大（上面那种刚拉到的情况），再丢弃一轮
+        while cd_buffer.front().is_some_and(|&(s, _)| s > cd_upper) {
+            cd_buffer.pop_front();
+        }
+
+        // 缓冲区按和值降序排列，从队首往后扫，跌破窗口下界就停——剩下更小
+        // 的候选不丢弃，留给之后更大的A⊕B（窗口下界更低）可能用到
+        for (cd_sum, cd_indices) in cd_buffer.iter() {
+            if *cd_sum < cd_lower {
+                break;
+            }
+
+            let mut combined = ab_indices.clone();
+            combined.extend_from_slice(cd_indices);
+            results.push(combined);
+
+            if !find_all {
+                return results;
+            }
+        }
+    }
+
+    results
+}
+
+/// 枚举 `indices` 指定的元素子集的所有子集和，返回 (和值, 原始索引列表)
+fn all_subset_sums(numbers: &[i64], indices: &[usize]) -> Vec<(i64, Vec<usize>)> {
+    let m = indices.len();
+    let mut sums = Vec::with_capacity(1 << m);
+
+    for mask in 0..(1u32 << m) {
+        let mut sum = 0;
+        let mut subset = Vec::new();
+        for (bit, &idx) in indices.iter().enumerate() {
+            if (mask & (1 << bit)) != 0 {
+                sum += numbers[idx];
+                subset.push(idx);
+            }
+        }
+        sums.push((sum, subset));
+    }
+
+    sums
+}
+
+/// 在两个已排序的(和值, 索引列表)数组上惰性按和值升序生成A⊕B的组合
+///
+/// 内部只维护一个大小为 `|sums_a|` 的小顶堆，不会把 `|sums_a| * |sums_b|`
+/// 种组合全部materialize出来。
+struct AscendingPairStream<'a> {
+    sums_a: &'a [(i64, Vec<usize>)],
+    sums_b: &'a [(i64, Vec<usize>)],
+    heap: std::collections::BinaryHeap<std::cmp::Reverse<(i64, usize, usize)>>,
+}
+
+impl<'a> AscendingPairStream<'a> {
+    fn new(sums_a: &'a [(i64, Vec<usize>)], sums_b: &'a [(i64, Vec<usize>)]) -> Self {
+        let mut heap = std::collections::BinaryHeap::with_capacity(sums_a.len());
+        if !sums_b.is_empty() {
+            for (i, (a_sum, _)) in sums_a.iter().enumerate() {
+                heap.push(std::cmp::Reverse((a_sum + sums_b[0].0, i, 0)));
+            }
+        }
+        AscendingPairStream { sums_a, sums_b, heap }
+    }
+
+    fn next(&mut self) -> Option<(i64, Vec<usize>)> {
+        let std::cmp::Reverse((sum, i, j)) = self.heap.pop()?;
+
+        if j + 1 < self.sums_b.len() {
+            self.heap.push(std::cmp::Reverse((
+                self.sums_a[i].0 + self.sums_b[j + 1].0,
+                i,
+                j + 1,
+            )));
+        }
+
+        let mut indices = self.sums_a[i].1.clone();
+        indices.extend_from_slice(&self.sums_b[j].1);
+        Some((sum, indices))
+    }
+}
+
+/// 在两个已按和值升序排好的(和值, 索引列表)数组上，惰性按和值降序生成C⊕D的组合
+///
+/// 和`AscendingPairStream`对称：只维护一个大小为`sums_c.len()`的大顶堆，初始时
+/// 把每个C的和跟`sums_d`里最大的那个（末尾）配对，每次弹出堆顶后把对应的D
+/// 下标往前移一位换更小的D，堆顶始终是当前还没吐出过的最大组合和
+struct DescendingPairStream<'a> {
+    sums_c: &'a [(i64, Vec<usize>)],
+    sums_d: &'a [(i64, Vec<usize>)],
+    heap: std::collections::BinaryHeap<(i64, usize, usize)>,
+}
+
+impl<'a> DescendingPairStream<'a> {
+    fn new(sums_c: &'a [(i64, Vec<usize>)], sums_d: &'a [(i64, Vec<usize>)]) -> Self {
+        let mut heap = std::collections::BinaryHeap::with_capacity(sums_c.len());
+        if !sums_d.is_empty() {
+            let last = sums_d.len() - 1;
+            for (i, (c_sum, _)) in sums_c.iter().enumerate() {
+                heap.push((c_sum + sums_d[last].0, i, last));
+            }
+        }
+        DescendingPairStream { sums_c, sums_d, heap }
+    }
+
+    fn next(&mut self) -> Option<(i64, Vec<usize>)> {
+        let (sum, i, j) = self.heap.pop()?;
+
+        if j > 0 {
+            self.heap.push((self.sums_c[i].0 + self.sums_d[j - 1].0, i, j - 1));
+        }
+
+        let mut indices = self.sums_c[i].1.clone();
+        indices.extend_from_slice(&self.sums_d[j].1);
+        Some((sum, indices))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_meet_middle_simple() {
         let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8];
@@ -259,4 +613,84 @@ mod tests {
             assert_eq!(sum, target);
         }
     }
+
+    #[test]
+    fn test_schroeppel_shamir_simple() {
+        let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let target = 20;
+        let precision = 0;
+
+        let results = find_subset_sum_schroeppel_shamir_raw(&numbers, target, precision, true);
+
+        assert!(!results.is_empty());
+        for indices in &results {
+            let sum: i64 = indices.iter().map(|&i| numbers[i]).sum();
+            assert_eq!(sum, target);
+        }
+    }
+
+    #[test]
+    fn test_schroeppel_shamir_negative_numbers_match_brute_force() {
+        // A⊕B 超过 upper_bound 时，若 C⊕D 能取到很负的和，仍然可能命中target，
+        // 不能一见ab_sum越界就整体断流
+        let numbers = vec![-5, 3, -2, 8, 1, -4, 6, 2];
+        let target = 0;
+        let precision = 0;
+
+        let results = find_subset_sum_schroeppel_shamir_raw(&numbers, target, precision, true);
+        for indices in &results {
+            let sum: i64 = indices.iter().map(|&i| numbers[i]).sum();
+            assert_eq!(sum, target);
+        }
+
+        let n = numbers.len();
+        let mut brute_force_count = 0;
+        for mask in 0..(1u64 << n) {
+            let sum: i64 = (0..n).filter(|&i| (mask & (1 << i)) != 0).map(|i| numbers[i]).sum();
+            if sum == target {
+                brute_force_count += 1;
+            }
+        }
+
+        assert_eq!(results.len(), brute_force_count);
+    }
+
+    #[test]
+    fn test_schroeppel_shamir_tied_sums_match_brute_force() {
+        // 大量重复元素会让很多C⊕D组合的和值相同：如果拉取C⊕D候选时一见
+        // 和值等于窗口下界就提前停止，会漏掉排在它后面、和值同样等于下界
+        // 的组合
+        let numbers = vec![1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1];
+        let target = 5;
+        let precision = 0;
+
+        let results = find_subset_sum_schroeppel_shamir_raw(&numbers, target, precision, true);
+        for indices in &results {
+            let sum: i64 = indices.iter().map(|&i| numbers[i]).sum();
+            assert_eq!(sum, target);
+        }
+
+        let n = numbers.len();
+        let mut brute_force_count = 0;
+        for mask in 0..(1u64 << n) {
+            let sum: i64 = (0..n).filter(|&i| (mask & (1 << i)) != 0).map(|i| numbers[i]).sum();
+            if sum == target {
+                brute_force_count += 1;
+            }
+        }
+
+        assert_eq!(results.len(), brute_force_count);
+    }
+
+    #[test]
+    fn test_meet_middle_count_matches_raw_len() {
+        let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let target = 15;
+        let precision = 0;
+
+        let results = find_subset_sum_meet_middle_raw(&numbers, target, precision, true);
+        let count = find_subset_sum_meet_middle_count_raw(&numbers, target, precision);
+
+        assert_eq!(count, results.len() as u64);
+    }
 }